@@ -0,0 +1,163 @@
+//! Spawn-environment normalization for Linux launches.
+//!
+//! Arcadia itself may run inside a Flatpak, Snap or AppImage sandbox, whose
+//! `PATH`/`LD_LIBRARY_PATH`/`GST_PLUGIN_PATH` entries point into the bundle
+//! rather than the host system. A game launched as a direct child inherits
+//! that environment unless we clean it first.
+
+use std::collections::HashMap;
+
+/// Environment variables whose colon-separated path lists get scrubbed of
+/// bundle-injected entries before a game is spawned.
+const PATH_LIKE_VARS: &[&str] = &["PATH", "LD_LIBRARY_PATH", "GST_PLUGIN_PATH", "GST_PLUGIN_SYSTEM_PATH"];
+
+/// The `XDG_*` variables captured when Arcadia started, before any bundle
+/// runtime had a chance to rewrite them.
+pub struct EnvSnapshot {
+    xdg_vars: HashMap<String, String>,
+}
+
+impl EnvSnapshot {
+    /// Captures the current `XDG_*` environment. Call this as early as
+    /// possible (extension construction), before launching any game.
+    pub fn capture() -> Self {
+        let xdg_vars = std::env::vars()
+            .filter(|(key, _)| key.starts_with("XDG_"))
+            .collect();
+        Self { xdg_vars }
+    }
+
+    /// Applies the captured `XDG_*` variables onto a command about to be
+    /// spawned, overriding whatever the sandbox runtime currently has set.
+    pub fn restore_xdg_vars(&self, command: &mut std::process::Command) {
+        for (key, value) in &self.xdg_vars {
+            command.env(key, value);
+        }
+    }
+}
+
+pub fn is_flatpak() -> bool {
+    std::path::Path::new("/.flatpak-info").exists() || std::env::var_os("FLATPAK_ID").is_some()
+}
+
+pub fn is_snap() -> bool {
+    std::env::var_os("SNAP").is_some()
+}
+
+pub fn is_appimage() -> bool {
+    std::env::var_os("APPIMAGE").is_some()
+}
+
+/// True if Arcadia itself is running inside any of the sandboxed bundle
+/// formats that rewrite the process environment.
+pub fn is_bundled() -> bool {
+    is_flatpak() || is_snap() || is_appimage()
+}
+
+/// Path fragments that identify a bundle-owned directory, used to filter
+/// them out of colon-separated path lists.
+fn bundle_markers() -> Vec<String> {
+    let mut markers = Vec::new();
+    if is_flatpak() {
+        markers.push("/app/".to_string());
+    }
+    if let Some(snap) = std::env::var_os("SNAP") {
+        markers.push(snap.to_string_lossy().into_owned());
+    }
+    if let Some(appdir) = std::env::var_os("APPDIR") {
+        markers.push(appdir.to_string_lossy().into_owned());
+    }
+    markers
+}
+
+/// Strips bundle-owned entries out of a colon-separated path list, then
+/// de-duplicates the remainder while preserving order. When a value repeats,
+/// only its lowest-priority (rightmost) occurrence is kept, so a
+/// sandbox-injected copy earlier in the list can't shadow the real one.
+fn clean_path_list(value: &str, markers: &[String]) -> Option<String> {
+    let entries: Vec<&str> = value
+        .split(':')
+        .filter(|entry| !entry.is_empty() && !markers.iter().any(|marker| entry.contains(marker.as_str())))
+        .collect();
+
+    let mut last_index = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        last_index.insert(*entry, i);
+    }
+    let deduped: Vec<&str> = entries
+        .iter()
+        .enumerate()
+        .filter(|(i, entry)| last_index[*entry] == *i)
+        .map(|(_, entry)| *entry)
+        .collect();
+
+    if deduped.is_empty() {
+        None
+    } else {
+        Some(deduped.join(":"))
+    }
+}
+
+/// Normalizes `command`'s environment for a Linux launch: scrubs
+/// bundle-injected entries from path-like variables (unsetting ones that end
+/// up empty), then restores the `XDG_*` variables captured at startup.
+///
+/// No-op when Arcadia isn't itself running inside a Flatpak/Snap/AppImage.
+pub fn normalize_for_spawn(command: &mut std::process::Command, snapshot: &EnvSnapshot) {
+    if !is_bundled() {
+        return;
+    }
+
+    let markers = bundle_markers();
+    for var in PATH_LIKE_VARS {
+        let Ok(value) = std::env::var(var) else {
+            continue;
+        };
+        match clean_path_list(&value, &markers) {
+            Some(cleaned) => {
+                command.env(var, cleaned);
+            }
+            None => {
+                command.env_remove(var);
+            }
+        }
+    }
+
+    snapshot.restore_xdg_vars(command);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_path_list_strips_bundle_marker_entries() {
+        let markers = vec!["/app/".to_string()];
+        let value = "/app/bin:/usr/bin:/app/lib/bin";
+        assert_eq!(clean_path_list(value, &markers), Some("/usr/bin".to_string()));
+    }
+
+    #[test]
+    fn clean_path_list_drops_empty_entries() {
+        let markers: Vec<String> = Vec::new();
+        let value = "/usr/bin::/usr/local/bin:";
+        assert_eq!(clean_path_list(value, &markers), Some("/usr/bin:/usr/local/bin".to_string()));
+    }
+
+    #[test]
+    fn clean_path_list_keeps_rightmost_duplicate() {
+        // When a path repeats, the earlier (leftmost) occurrence is the one
+        // that gets dropped, so a sandbox-injected copy ahead of the real
+        // entry can't shadow it by winning the dedup.
+        let markers: Vec<String> = Vec::new();
+        let value = "/usr/bin:/usr/local/bin:/usr/bin";
+        assert_eq!(clean_path_list(value, &markers), Some("/usr/local/bin:/usr/bin".to_string()));
+    }
+
+    #[test]
+    fn clean_path_list_returns_none_when_everything_is_filtered() {
+        let markers = vec!["/app/".to_string()];
+        let value = "/app/bin:/app/lib/bin";
+        assert_eq!(clean_path_list(value, &markers), None);
+    }
+}
@@ -1,3 +1,8 @@
+mod env;
+mod shortcuts;
+mod users;
+mod vdf;
+
 use arcadia_extension_framework::{
     models::{ExtensionManifest, ExtensionType},
     traits::{ExtensionImpl, ExtensionContext},
@@ -6,12 +11,96 @@ use arcadia_extension_framework::{
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use shortcuts::SteamShortcut;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::fs;
+use tokio::sync::RwLock;
+use vdf::VdfValue;
 
 // Steam-specific data structures
 
+/// A single entry from an app's `appinfo.vdf` `config.launch` table, i.e. one
+/// way the game can be started (main binary, server tool, VR mode, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchEntry {
+    pub executable: Option<String>,
+    pub arguments: Option<String>,
+    pub working_dir: Option<String>,
+    pub description: Option<String>,
+    pub os_list: Option<String>,
+}
+
+/// Coarse install status derived from the ACF `StateFlags` bitfield.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InstallState {
+    FullyInstalled,
+    Downloading,
+    Validating,
+    UpdateRequired,
+    Uninstalled,
+}
+
+impl InstallState {
+    // Bit values from Steam's ACF `StateFlags`; see SteamKit's `EAppState`.
+    const UPDATE_REQUIRED: u32 = 2;
+    const FULLY_INSTALLED: u32 = 4;
+    const FILES_MISSING: u32 = 32;
+    const FILES_CORRUPT: u32 = 128;
+    const UPDATE_RUNNING: u32 = 256;
+    const UPDATE_STARTED: u32 = 1024;
+    const UNINSTALLING: u32 = 2048;
+    const RECONFIGURING: u32 = 65536;
+    const VALIDATING: u32 = 131072;
+    const ADDING_FILES: u32 = 262144;
+    const PREALLOCATING: u32 = 524288;
+    const DOWNLOADING: u32 = 1048576;
+    const STAGING: u32 = 2097152;
+    const COMMITTING: u32 = 4194304;
+    const UPDATE_STOPPING: u32 = 8388608;
+
+    /// Classifies a raw `StateFlags` value into one [`InstallState`].
+    /// `FullyInstalled` only wins when no in-progress bit and no pending
+    /// update is also set, so a game mid-update (or just flagged for one) is
+    /// reported as `Downloading`/`Validating`/`UpdateRequired`, not done.
+    pub fn from_flags(flags: u32) -> Self {
+        let in_progress = Self::UPDATE_RUNNING
+            | Self::UPDATE_STARTED
+            | Self::UNINSTALLING
+            | Self::RECONFIGURING
+            | Self::VALIDATING
+            | Self::ADDING_FILES
+            | Self::PREALLOCATING
+            | Self::DOWNLOADING
+            | Self::STAGING
+            | Self::COMMITTING
+            | Self::UPDATE_STOPPING;
+
+        if flags & Self::FULLY_INSTALLED != 0 && flags & (in_progress | Self::UPDATE_REQUIRED) == 0 {
+            InstallState::FullyInstalled
+        } else if flags & (Self::DOWNLOADING | Self::PREALLOCATING | Self::UPDATE_STARTED | Self::UPDATE_RUNNING) != 0 {
+            InstallState::Downloading
+        } else if flags
+            & (Self::STAGING
+                | Self::COMMITTING
+                | Self::VALIDATING
+                | Self::ADDING_FILES
+                | Self::RECONFIGURING
+                | Self::UNINSTALLING
+                | Self::UPDATE_STOPPING
+                | Self::FILES_MISSING
+                | Self::FILES_CORRUPT)
+            != 0
+        {
+            InstallState::Validating
+        } else if flags & Self::UPDATE_REQUIRED != 0 {
+            InstallState::UpdateRequired
+        } else {
+            InstallState::Uninstalled
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SteamApp {
     pub appid: u32,
@@ -20,6 +109,38 @@ pub struct SteamApp {
     pub size_on_disk: Option<u64>,
     pub last_updated: Option<u64>,
     pub launch_options: Option<String>,
+    /// Display name as advertised on the Steam store (`common.name` in
+    /// `appinfo.vdf`), which can differ from the ACF `name`.
+    pub store_name: Option<String>,
+    /// All launch configurations found for this app in `appinfo.vdf`.
+    #[serde(default)]
+    pub launch_entries: Vec<LaunchEntry>,
+    #[serde(default = "default_install_state")]
+    pub install_state: InstallState,
+    pub bytes_downloaded: Option<u64>,
+    pub bytes_to_download: Option<u64>,
+    /// SteamID64 of the last account to play this app (ACF `LastOwner`).
+    pub last_user: Option<u64>,
+}
+
+fn default_install_state() -> InstallState {
+    InstallState::Uninstalled
+}
+
+/// How `launch_game` should start a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchMode {
+    /// Hand off to the Steam client via `steam://rungameid`, so Steam
+    /// arranges Proton/Wine, the overlay and cloud sync itself.
+    SteamProtocol,
+    /// Spawn the resolved executable directly, bypassing Steam.
+    DirectSpawn,
+}
+
+impl Default for LaunchMode {
+    fn default() -> Self {
+        LaunchMode::SteamProtocol
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +163,20 @@ pub struct SteamExtension {
     manifest: ExtensionManifest,
     libraries: Vec<SteamLibrary>,
     steam_install_path: Option<PathBuf>,
+    /// Parsed `appcache/appinfo.vdf` entries, keyed by appid, used to enrich
+    /// [`SteamApp`] with store metadata that ACF manifests don't carry.
+    app_info: HashMap<u32, vdf::AppInfoEntry>,
+    /// Non-Steam shortcuts for the active user, behind a lock since
+    /// `add_shortcut`/`remove_shortcut` mutate it from `&self`.
+    shortcuts: RwLock<Vec<SteamShortcut>>,
+    shortcuts_path: Option<PathBuf>,
+    /// `XDG_*` variables captured at construction, restored onto launched
+    /// games so a Flatpak/Snap/AppImage sandbox can't leak into them.
+    env_snapshot: env::EnvSnapshot,
+    /// Local Steam accounts detected from `config/loginusers.vdf`.
+    users: Vec<users::SteamUser>,
+    /// Per-appid `LaunchOptions` from the active user's `localconfig.vdf`.
+    launch_options: HashMap<u32, String>,
 }
 
 impl SteamExtension {
@@ -59,6 +194,11 @@ impl SteamExtension {
                 "scan_games".to_string(),
                 "get_game_details".to_string(),
                 "launch_game".to_string(),
+                "add_shortcut".to_string(),
+                "remove_shortcut".to_string(),
+                "request_install".to_string(),
+                "launch_with".to_string(),
+                "list_users".to_string(),
             ]),
             apis: Some(serde_json::from_str(r#"{"provided": ["steam_games", "steam_launcher"]}"#).unwrap()),
             menu_items: None,
@@ -68,6 +208,151 @@ impl SteamExtension {
             manifest,
             libraries: Vec::new(),
             steam_install_path: None,
+            app_info: HashMap::new(),
+            shortcuts: RwLock::new(Vec::new()),
+            shortcuts_path: None,
+            env_snapshot: env::EnvSnapshot::capture(),
+            users: Vec::new(),
+            launch_options: HashMap::new(),
+        }
+    }
+
+    /// Loads the local Steam account list and the active account's per-game
+    /// `LaunchOptions`, so `parse_app_manifest` can wire them into each
+    /// [`SteamApp`]. Missing user data is not fatal.
+    async fn load_user_data(&mut self) -> Result<(), ExtensionError> {
+        let steam_path = self.steam_install_path.as_ref()
+            .ok_or_else(|| ExtensionError::Validation("Steam path not set".to_string()))?
+            .clone();
+
+        let loginusers_path = steam_path.join("config").join("loginusers.vdf");
+        self.users = users::read_login_users(&loginusers_path).await?;
+
+        let Some(active_user) = users::most_recent_user(&self.users) else {
+            return Ok(());
+        };
+        let account_id = users::steamid64_to_account_id(active_user.steam_id64);
+        let localconfig_path = steam_path
+            .join("userdata")
+            .join(account_id.to_string())
+            .join("config")
+            .join("localconfig.vdf");
+        self.launch_options = users::read_launch_options(&localconfig_path).await?;
+
+        Ok(())
+    }
+
+    /// Finds the `userdata/<steamid3>/config` directory for the same active
+    /// account `load_user_data` resolves via `most_recent_user`, so shortcuts
+    /// are read from and written into the right account.
+    async fn userdata_config_dir(&self) -> Result<PathBuf, ExtensionError> {
+        let steam_path = self.steam_install_path.as_ref()
+            .ok_or_else(|| ExtensionError::Validation("Steam path not set".to_string()))?;
+
+        let active_user = users::most_recent_user(&self.users)
+            .ok_or_else(|| ExtensionError::NotFound("No Steam userdata directory found".to_string()))?;
+        let account_id = users::steamid64_to_account_id(active_user.steam_id64);
+
+        Ok(steam_path.join("userdata").join(account_id.to_string()).join("config"))
+    }
+
+    /// Loads `shortcuts.vdf` for the active user, if one exists. Having no
+    /// userdata directory yet (e.g. Steam never logged in) is not fatal.
+    async fn load_shortcuts(&mut self) -> Result<(), ExtensionError> {
+        let config_dir = match self.userdata_config_dir().await {
+            Ok(dir) => dir,
+            Err(_) => return Ok(()),
+        };
+
+        let path = config_dir.join("shortcuts.vdf");
+        let loaded = shortcuts::read_shortcuts(&path).await?;
+        *self.shortcuts.write().await = loaded;
+        self.shortcuts_path = Some(path);
+        Ok(())
+    }
+
+    /// Builds the [`SteamGame`] view of a non-Steam shortcut so it can be
+    /// returned alongside regular library games from `scan_games`.
+    fn shortcut_to_game(&self, shortcut: &SteamShortcut) -> SteamGame {
+        SteamGame {
+            app: SteamApp {
+                appid: shortcut.appid,
+                name: shortcut.app_name.clone(),
+                install_dir: None,
+                size_on_disk: None,
+                last_updated: None,
+                launch_options: shortcut.launch_options.clone(),
+                store_name: None,
+                launch_entries: Vec::new(),
+                // Non-Steam shortcuts point at an executable that already
+                // exists on disk; there's no Steam-managed install to track.
+                install_state: InstallState::FullyInstalled,
+                bytes_downloaded: None,
+                bytes_to_download: None,
+                last_user: None,
+            },
+            executable: Some(shortcut.exe.clone()),
+            working_dir: shortcut.start_dir.clone(),
+            launch_args: shortcut.launch_options.clone(),
+            icon_path: shortcut.icon.clone(),
+            banner_path: None,
+        }
+    }
+
+    /// Loads and indexes `appcache/appinfo.vdf`. Missing or unreadable files
+    /// are not fatal: ACF-derived data alone is still usable, just missing
+    /// store name / launch entries.
+    async fn load_app_info(&mut self) -> Result<(), ExtensionError> {
+        let steam_path = self.steam_install_path.as_ref()
+            .ok_or_else(|| ExtensionError::Validation("Steam path not set".to_string()))?;
+
+        let appinfo_path = steam_path.join("appcache").join("appinfo.vdf");
+        if !appinfo_path.exists() {
+            return Ok(());
+        }
+
+        let bytes = fs::read(&appinfo_path).await?;
+        let entries = vdf::parse_appinfo(&bytes)?;
+        self.app_info = entries.into_iter().map(|entry| (entry.app_id, entry)).collect();
+        Ok(())
+    }
+
+    /// Pulls the store name and launch configurations for `appid` out of the
+    /// cached `appinfo.vdf` data, if present.
+    fn enrich_from_app_info(&self, app: &mut SteamApp) {
+        let Some(entry) = self.app_info.get(&app.appid) else {
+            return;
+        };
+
+        app.store_name = entry.data.get_str(&["common", "name"]).map(str::to_string);
+        if app.last_updated.is_none() {
+            app.last_updated = Some(entry.last_updated as u64);
+        }
+
+        if let Some(VdfValue::Table(launch_table)) = entry.data.get_path(&["config", "launch"]) {
+            let mut entries: Vec<(String, LaunchEntry)> = launch_table
+                .iter()
+                .filter_map(|(index, value)| {
+                    let table = value.as_table()?;
+                    Some((
+                        index.clone(),
+                        LaunchEntry {
+                            executable: table.get("executable").and_then(VdfValue::as_str).map(str::to_string),
+                            arguments: table.get("arguments").and_then(VdfValue::as_str).map(str::to_string),
+                            working_dir: table.get("workingdir").and_then(VdfValue::as_str).map(str::to_string),
+                            description: table.get("description").and_then(VdfValue::as_str).map(str::to_string),
+                            os_list: table
+                                .get("config")
+                                .and_then(VdfValue::as_table)
+                                .and_then(|c| c.get("oslist"))
+                                .and_then(VdfValue::as_str)
+                                .map(str::to_string),
+                        },
+                    ))
+                })
+                .collect();
+            entries.sort_by_key(|(index, _)| index.parse::<u32>().unwrap_or(u32::MAX));
+            app.launch_entries = entries.into_iter().map(|(_, entry)| entry).collect();
         }
     }
 
@@ -110,24 +395,47 @@ impl SteamExtension {
 
     async fn scan_steam_libraries(&mut self) -> Result<(), ExtensionError> {
         let steam_path = self.steam_install_path.as_ref()
-            .ok_or_else(|| ExtensionError::Validation("Steam path not set".to_string()))?;
+            .ok_or_else(|| ExtensionError::Validation("Steam path not set".to_string()))?
+            .clone();
 
-        let _config_path = if cfg!(target_os = "windows") {
-            steam_path.join("config").join("config.vdf")
-        } else {
-            steam_path.join("config").join("config.vdf")
-        };
+        let mut library_roots = Vec::new();
 
-        // For simplicity, assume default library path
-        let default_library = steam_path.join("steamapps");
-        if default_library.exists() {
-            let library = SteamLibrary {
-                path: default_library,
-                apps: HashMap::new(),
-            };
-            self.libraries.push(library);
+        // The main install's own steamapps directory is always a library,
+        // even if it's missing from libraryfolders.vdf.
+        let default_steamapps = steam_path.join("steamapps");
+        if default_steamapps.exists() {
+            library_roots.push(default_steamapps);
+        }
+
+        let libraryfolders_path = steam_path.join("config").join("libraryfolders.vdf");
+        if libraryfolders_path.exists() {
+            let content = fs::read_to_string(&libraryfolders_path).await?;
+            let root = vdf::parse_text(&content)?;
+            let folders = root.get_path(&["libraryfolders"]).unwrap_or(&root);
+
+            if let Some(table) = folders.as_table() {
+                let mut indices: Vec<&String> = table.keys().collect();
+                indices.sort_by_key(|key| key.parse::<u32>().unwrap_or(u32::MAX));
+                for index in indices {
+                    let Some(entry) = table.get(index).and_then(VdfValue::as_table) else {
+                        continue;
+                    };
+                    let Some(path_str) = entry.get("path").and_then(VdfValue::as_str) else {
+                        continue;
+                    };
+                    let steamapps = PathBuf::from(path_str).join("steamapps");
+                    if steamapps.exists() && !library_roots.contains(&steamapps) {
+                        library_roots.push(steamapps);
+                    }
+                }
+            }
         }
 
+        self.libraries = library_roots
+            .into_iter()
+            .map(|path| SteamLibrary { path, apps: HashMap::new() })
+            .collect();
+
         Ok(())
     }
 
@@ -147,46 +455,44 @@ impl SteamExtension {
 
     async fn parse_app_manifest(&self, path: &PathBuf) -> Result<Option<SteamApp>, ExtensionError> {
         let content = fs::read_to_string(path).await?;
-        // Simple VDF parsing (Valve Data Format)
-        // This is a basic implementation - real VDF parsing would be more complex
-        let appid = self.extract_vdf_value(&content, "appid")?;
-        let name = self.extract_vdf_value(&content, "name")?;
-        let install_dir = self.extract_vdf_value(&content, "installdir").ok();
-        let size_on_disk = self.extract_vdf_value(&content, "SizeOnDisk")
-            .ok()
-            .and_then(|s| s.parse().ok());
-
-        let app = SteamApp {
+        let root = vdf::parse_text(&content)?;
+        let state = root.get_path(&["AppState"]).unwrap_or(&root);
+
+        let appid = state.get_str(&["appid"])
+            .ok_or_else(|| ExtensionError::Validation("Key appid not found".to_string()))?;
+        let name = state.get_str(&["name"])
+            .ok_or_else(|| ExtensionError::Validation("Key name not found".to_string()))?
+            .to_string();
+        let install_dir = state.get_str(&["installdir"]).map(str::to_string);
+        let size_on_disk = state.get_str(&["SizeOnDisk"]).and_then(|s| s.parse().ok());
+        let last_updated = state.get_str(&["LastUpdated"]).and_then(|s| s.parse().ok());
+        let state_flags = state.get_str(&["StateFlags"]).and_then(|s| s.parse::<u32>().ok()).unwrap_or(0);
+        let bytes_downloaded = state.get_str(&["BytesDownloaded"]).and_then(|s| s.parse().ok());
+        let bytes_to_download = state.get_str(&["BytesToDownload"]).and_then(|s| s.parse().ok());
+        let last_user = state.get_str(&["LastOwner"]).and_then(|s| s.parse().ok());
+
+        let mut app = SteamApp {
             appid: appid.parse().map_err(|_| ExtensionError::Validation("Invalid appid".to_string()))?,
             name,
             install_dir,
             size_on_disk,
-            last_updated: None,
+            last_updated,
             launch_options: None,
+            store_name: None,
+            launch_entries: Vec::new(),
+            install_state: InstallState::from_flags(state_flags),
+            bytes_downloaded,
+            bytes_to_download,
+            last_user,
         };
+        self.enrich_from_app_info(&mut app);
+        if let Some(launch_options) = self.launch_options.get(&app.appid) {
+            app.launch_options = Some(launch_options.clone());
+        }
 
         Ok(Some(app))
     }
 
-    fn extract_vdf_value(&self, content: &str, key: &str) -> Result<String, ExtensionError> {
-        // Very basic VDF extraction - in reality, use a proper VDF parser
-        for line in content.lines() {
-            let line = line.trim();
-            if line.contains(&format!("\"{}\"", key)) {
-                if let Some(start) = line.find(&format!("\"{}\"", key)) {
-                    let after_key = &line[start + key.len() + 2..];
-                    if let Some(quote_start) = after_key.find('"') {
-                        let after_quote = &after_key[quote_start + 1..];
-                        if let Some(quote_end) = after_quote.find('"') {
-                            return Ok(after_quote[..quote_end].to_string());
-                        }
-                    }
-                }
-            }
-        }
-        Err(ExtensionError::Validation(format!("Key {} not found", key)))
-    }
-
     async fn get_game_details(&self, appid: u32) -> Result<SteamGame, ExtensionError> {
         for library in &self.libraries {
             if let Some(app) = library.apps.get(&appid) {
@@ -198,7 +504,7 @@ impl SteamExtension {
                     app: app.clone(),
                     executable,
                     working_dir: Some(game_dir.to_string_lossy().to_string()),
-                    launch_args: None,
+                    launch_args: app.launch_options.clone(),
                     icon_path: icon_path.unwrap_or(None),
                     banner_path: None,
                 };
@@ -245,18 +551,291 @@ impl SteamExtension {
         Ok(None)
     }
 
-    async fn launch_game(&self, appid: u32) -> Result<(), ExtensionError> {
+    /// Launches `appid` either through the Steam client (default, lets Steam
+    /// set up Proton/Wine, overlay and cloud sync) or by spawning the
+    /// executable directly.
+    async fn launch_game(&self, appid: u32, mode: LaunchMode) -> Result<(), ExtensionError> {
+        match mode {
+            LaunchMode::SteamProtocol => open_steam_url(&format!("steam://rungameid/{}", appid)),
+            LaunchMode::DirectSpawn => self.launch_game_direct(appid).await,
+        }
+    }
+
+    async fn launch_game_direct(&self, appid: u32) -> Result<(), ExtensionError> {
         let game = self.get_game_details(appid).await?;
-        if let Some(executable) = game.executable {
-            // Use std::process::Command to launch the game
-            std::process::Command::new(&executable)
-                .current_dir(game.working_dir.as_ref().unwrap_or(&".".to_string()))
-                .spawn()
-                .map_err(|e| ExtensionError::Io(e))?;
-            Ok(())
+        let executable = game.executable
+            .ok_or_else(|| ExtensionError::Validation("No executable found for game".to_string()))?;
+
+        let mut argv = build_launch_argv(&executable, game.app.launch_options.as_deref());
+        let mut proton_env = None;
+
+        if cfg!(target_os = "linux") {
+            if let Some((tool_name, tool_dir)) = self.detect_compat_tool(appid).await {
+                if tool_dir.join("proton").exists() {
+                    proton_env = self.steam_install_path.clone().zip(self.compat_data_path(appid));
+                }
+                argv = wrap_with_compat_tool(&tool_name, &tool_dir, argv);
+            }
+        }
+
+        let program = argv.remove(0);
+        let mut command = std::process::Command::new(program);
+        command
+            .args(argv)
+            .current_dir(game.working_dir.as_ref().unwrap_or(&".".to_string()));
+
+        // Proton's `run` wrapper needs these to locate the Steam client and
+        // create/find the per-app compat prefix; without them it exits
+        // immediately instead of launching the game.
+        if let Some((client_install_path, compat_data_path)) = &proton_env {
+            fs::create_dir_all(compat_data_path).await?;
+            command
+                .env("STEAM_COMPAT_CLIENT_INSTALL_PATH", client_install_path)
+                .env("STEAM_COMPAT_DATA_PATH", compat_data_path);
+        }
+
+        if cfg!(target_os = "linux") {
+            env::normalize_for_spawn(&mut command, &self.env_snapshot);
+        }
+
+        command.spawn().map_err(ExtensionError::Io)?;
+        Ok(())
+    }
+
+    /// The per-app Proton compat prefix directory: `<library>/steamapps/compatdata/<appid>`,
+    /// resolved from whichever library actually has the app installed.
+    fn compat_data_path(&self, appid: u32) -> Option<PathBuf> {
+        self.libraries.iter()
+            .find(|library| library.apps.contains_key(&appid))
+            .map(|library| library.path.join("compatdata").join(appid.to_string()))
+    }
+
+    /// Opens the game's install directory, or a file inside it, through the
+    /// system's default handler (file manager, editor, etc).
+    async fn launch_with(&self, appid: u32, relative_file: Option<String>) -> Result<(), ExtensionError> {
+        let game = self.get_game_details(appid).await?;
+        let install_dir = game.working_dir
+            .ok_or_else(|| ExtensionError::Validation("No install directory found for game".to_string()))?;
+
+        let target = match relative_file {
+            Some(file) => {
+                let file_path = PathBuf::from(&file);
+                if file_path.is_absolute() {
+                    file_path
+                } else {
+                    PathBuf::from(install_dir).join(file_path)
+                }
+            }
+            None => PathBuf::from(install_dir),
+        };
+
+        open_with_system_handler(target.as_os_str())
+    }
+
+    /// Looks up the Proton/Wine compatibility tool Steam has configured for
+    /// `appid` in `config/config.vdf`'s `CompatToolMapping`, resolving it to
+    /// an install directory under `compatibilitytools.d` or
+    /// `steamapps/common`.
+    async fn detect_compat_tool(&self, appid: u32) -> Option<(String, PathBuf)> {
+        let steam_path = self.steam_install_path.as_ref()?;
+        let content = fs::read_to_string(steam_path.join("config").join("config.vdf")).await.ok()?;
+        let root = vdf::parse_text(&content).ok()?;
+
+        let appid_key = appid.to_string();
+        let tool_name = root.get_str(&[
+            "InstallConfigStore", "Software", "Valve", "Steam", "CompatToolMapping",
+            &appid_key, "name",
+        ])?;
+        if tool_name.is_empty() {
+            return None;
+        }
+
+        let from_d = steam_path.join("compatibilitytools.d").join(tool_name);
+        let tool_dir = if from_d.exists() {
+            from_d
         } else {
-            Err(ExtensionError::Validation("No executable found for game".to_string()))
+            steam_path.join("steamapps").join("common").join(tool_name)
+        };
+        Some((tool_name.to_string(), tool_dir))
+    }
+
+    /// Adds a non-Steam shortcut and persists `shortcuts.vdf`.
+    async fn add_shortcut(
+        &self,
+        app_name: String,
+        exe: String,
+        start_dir: Option<String>,
+        icon: Option<String>,
+    ) -> Result<SteamShortcut, ExtensionError> {
+        let path = self.shortcuts_path.as_ref()
+            .ok_or_else(|| ExtensionError::Validation("No Steam user loaded for shortcuts".to_string()))?;
+
+        let shortcut = SteamShortcut {
+            appid: shortcuts::compute_shortcut_appid(&exe, &app_name),
+            app_name,
+            exe,
+            start_dir,
+            icon,
+            launch_options: None,
+            is_hidden: false,
+            allow_desktop_config: true,
+            allow_overlay: true,
+            tags: Vec::new(),
+        };
+
+        let mut shortcuts = self.shortcuts.write().await;
+        shortcuts.retain(|s| s.appid != shortcut.appid);
+        shortcuts.push(shortcut.clone());
+        shortcuts::write_shortcuts(path, &shortcuts).await?;
+
+        Ok(shortcut)
+    }
+
+    /// Removes a non-Steam shortcut by appid and persists `shortcuts.vdf`.
+    async fn remove_shortcut(&self, appid: u32) -> Result<(), ExtensionError> {
+        let path = self.shortcuts_path.as_ref()
+            .ok_or_else(|| ExtensionError::Validation("No Steam user loaded for shortcuts".to_string()))?;
+
+        let mut shortcuts = self.shortcuts.write().await;
+        let before = shortcuts.len();
+        shortcuts.retain(|s| s.appid != appid);
+        if shortcuts.len() == before {
+            return Err(ExtensionError::NotFound(format!("Shortcut with appid {} not found", appid)));
+        }
+        shortcuts::write_shortcuts(path, &shortcuts).await?;
+
+        Ok(())
+    }
+
+    /// Triggers Steam's own installer for `appid` via the `steam://install`
+    /// protocol, then polls that app's ACF `StateFlags` until the
+    /// fully-installed bit is set or `install_wait_seconds` elapses.
+    async fn request_install(&self, appid: u32, install_wait_seconds: u64) -> Result<SteamApp, ExtensionError> {
+        open_steam_url(&format!("steam://install/{}", appid))?;
+
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(install_wait_seconds);
+        loop {
+            for library in &self.libraries {
+                let acf_path = library.path.join(format!("appmanifest_{}.acf", appid));
+                if !acf_path.exists() {
+                    continue;
+                }
+                if let Some(app) = self.parse_app_manifest(&acf_path).await? {
+                    if app.install_state == InstallState::FullyInstalled {
+                        return Ok(app);
+                    }
+                }
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(ExtensionError::Validation(format!(
+                    "Timed out waiting for appid {} to finish installing after {}s",
+                    appid, install_wait_seconds
+                )));
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        }
+    }
+}
+
+const DEFAULT_INSTALL_WAIT_SECONDS: u64 = 300;
+
+/// Opens a `steam://` protocol URL through the platform's default handler,
+/// letting the Steam client itself drive installs and launches.
+fn open_steam_url(url: &str) -> Result<(), ExtensionError> {
+    open_with_system_handler(url)
+}
+
+/// Opens any file, directory, or URL through the platform's default handler.
+fn open_with_system_handler(target: impl AsRef<std::ffi::OsStr>) -> Result<(), ExtensionError> {
+    let target = target.as_ref();
+    let result = if cfg!(target_os = "windows") {
+        std::process::Command::new("cmd").arg("/C").arg("start").arg("").arg(target).spawn()
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open").arg(target).spawn()
+    } else {
+        std::process::Command::new("xdg-open").arg(target).spawn()
+    };
+
+    result.map(|_| ()).map_err(ExtensionError::Io)
+}
+
+/// Builds the argv for a direct launch, substituting `%command%` in
+/// `launch_options` with the resolved executable (Steam's own convention for
+/// wrapping launches, e.g. `gamemoderun %command% -novid`). When there's no
+/// `%command%` placeholder, `launch_options` is just appended as extra args.
+fn build_launch_argv(executable: &str, launch_options: Option<&str>) -> Vec<String> {
+    match launch_options {
+        Some(opts) if opts.contains("%command%") => tokenize_launch_options(opts)
+            .into_iter()
+            .map(|token| if token == "%command%" { executable.to_string() } else { token })
+            .collect(),
+        Some(opts) if !opts.is_empty() => {
+            let mut argv = vec![executable.to_string()];
+            argv.extend(tokenize_launch_options(opts));
+            argv
+        }
+        _ => vec![executable.to_string()],
+    }
+}
+
+/// Splits a `LaunchOptions` string into argv tokens, honoring `"..."`
+/// quoting so a quoted path with spaces (e.g. `"/path/with space/tool"
+/// %command%`) survives as one token. Does not support escaped quotes inside
+/// a quoted segment, which real-world launch options don't use.
+fn tokenize_launch_options(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut token = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                break;
+            }
+            if c == '"' {
+                chars.next();
+                for c in chars.by_ref() {
+                    if c == '"' {
+                        break;
+                    }
+                    token.push(c);
+                }
+            } else {
+                token.push(c);
+                chars.next();
+            }
         }
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// Prefixes `argv` with whatever's needed to run it under the given
+/// compatibility tool: Proton's own `proton run` wrapper when present, or a
+/// bare `wine` invocation for Wine-based tools.
+fn wrap_with_compat_tool(tool_name: &str, tool_dir: &std::path::Path, argv: Vec<String>) -> Vec<String> {
+    let proton_script = tool_dir.join("proton");
+    if proton_script.exists() {
+        let mut full = vec![
+            "python3".to_string(),
+            proton_script.to_string_lossy().to_string(),
+            "run".to_string(),
+        ];
+        full.extend(argv);
+        full
+    } else if tool_name.to_ascii_lowercase().contains("wine") {
+        let mut full = vec!["wine".to_string()];
+        full.extend(argv);
+        full
+    } else {
+        argv
     }
 }
 
@@ -264,12 +843,17 @@ impl SteamExtension {
 impl ExtensionImpl for SteamExtension {
     async fn initialize(&mut self, _context: &ExtensionContext) -> Result<(), ExtensionError> {
         self.find_steam_install_path().await?;
+        self.load_app_info().await?;
+        self.load_user_data().await?;
         self.scan_steam_libraries().await?;
         let paths: Vec<PathBuf> = self.libraries.iter().map(|l| l.path.clone()).collect();
+        let mut seen_appids = std::collections::HashSet::new();
         for (i, path) in paths.into_iter().enumerate() {
-            let apps = self.scan_games_in_library(&path).await?;
+            let mut apps = self.scan_games_in_library(&path).await?;
+            apps.retain(|appid, _| seen_appids.insert(*appid));
             self.libraries[i].apps = apps;
         }
+        self.load_shortcuts().await?;
         Ok(())
     }
 
@@ -281,7 +865,7 @@ impl ExtensionImpl for SteamExtension {
     async fn handle_hook(&self, hook: &str, params: Value) -> Result<Value, ExtensionError> {
         match hook {
             "scan_games" => {
-                let games: Vec<SteamGame> = self.libraries.iter()
+                let mut games: Vec<SteamGame> = self.libraries.iter()
                     .flat_map(|lib| lib.apps.values())
                     .map(|app| SteamGame {
                         app: app.clone(),
@@ -292,6 +876,7 @@ impl ExtensionImpl for SteamExtension {
                         banner_path: None,
                     })
                     .collect();
+                games.extend(self.shortcuts.read().await.iter().map(|s| self.shortcut_to_game(s)));
                 Ok(serde_json::to_value(games)?)
             }
             "get_game_details" => {
@@ -305,9 +890,54 @@ impl ExtensionImpl for SteamExtension {
                 let appid = params.get("appid")
                     .and_then(|v| v.as_u64())
                     .ok_or_else(|| ExtensionError::Validation("appid parameter required".to_string()))?;
-                self.launch_game(appid as u32).await?;
+                let mode = match params.get("mode").and_then(|v| v.as_str()) {
+                    Some("direct") => LaunchMode::DirectSpawn,
+                    _ => LaunchMode::default(),
+                };
+                self.launch_game(appid as u32, mode).await?;
                 Ok(Value::Null)
             }
+            "add_shortcut" => {
+                let app_name = params.get("app_name")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| ExtensionError::Validation("app_name parameter required".to_string()))?
+                    .to_string();
+                let exe = params.get("exe")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| ExtensionError::Validation("exe parameter required".to_string()))?
+                    .to_string();
+                let start_dir = params.get("start_dir").and_then(|v| v.as_str()).map(str::to_string);
+                let icon = params.get("icon").and_then(|v| v.as_str()).map(str::to_string);
+
+                let shortcut = self.add_shortcut(app_name, exe, start_dir, icon).await?;
+                Ok(serde_json::to_value(shortcut)?)
+            }
+            "remove_shortcut" => {
+                let appid = params.get("appid")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| ExtensionError::Validation("appid parameter required".to_string()))?;
+                self.remove_shortcut(appid as u32).await?;
+                Ok(Value::Null)
+            }
+            "request_install" => {
+                let appid = params.get("appid")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| ExtensionError::Validation("appid parameter required".to_string()))?;
+                let install_wait_seconds = params.get("install_wait_seconds")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(DEFAULT_INSTALL_WAIT_SECONDS);
+                let app = self.request_install(appid as u32, install_wait_seconds).await?;
+                Ok(serde_json::to_value(app)?)
+            }
+            "launch_with" => {
+                let appid = params.get("appid")
+                    .and_then(|v| v.as_u64())
+                    .ok_or_else(|| ExtensionError::Validation("appid parameter required".to_string()))?;
+                let file = params.get("file").and_then(|v| v.as_str()).map(str::to_string);
+                self.launch_with(appid as u32, file).await?;
+                Ok(Value::Null)
+            }
+            "list_users" => Ok(serde_json::to_value(&self.users)?),
             _ => Err(ExtensionError::Validation(format!("Unknown hook: {}", hook))),
         }
     }
@@ -334,4 +964,54 @@ mod tests {
         let extension = SteamExtension::new();
         assert_eq!(extension.get_id(), "steam_extension");
     }
+
+    #[test]
+    fn install_state_from_flags() {
+        // Flag values below are named against SteamKit's `EAppState`, not the
+        // crate's own constants, so this table can't pass just because it
+        // agrees with `InstallState::from_flags`'s own bit table.
+        let cases = [
+            (0, InstallState::Uninstalled),                  // None
+            (1, InstallState::Uninstalled),                  // Uninstalled
+            (4, InstallState::FullyInstalled),                // FullyInstalled
+            (2, InstallState::UpdateRequired),                // UpdateRequired
+            // FullyInstalled with an update pending must not read as done.
+            (4 | 2, InstallState::UpdateRequired),             // FullyInstalled | UpdateRequired
+            // A real captured appmanifest mid-download: installed, update
+            // required, and actively fetching new bytes.
+            (4 | 2 | 256 | 1048576, InstallState::Downloading), // FullyInstalled | UpdateRequired | UpdateRunning | Downloading
+            (1024, InstallState::Downloading),                // UpdateStarted
+            (256, InstallState::Downloading),                 // UpdateRunning
+            (1048576, InstallState::Downloading),             // Downloading
+            (524288, InstallState::Downloading),              // Preallocating
+            (2097152, InstallState::Validating),              // Staging
+            (4194304, InstallState::Validating),              // Committing
+            (8388608, InstallState::Validating),              // UpdateStopping
+            (262144, InstallState::Validating),               // AddingFiles
+            (131072, InstallState::Validating),               // Validating
+            (65536, InstallState::Validating),                // Reconfiguring
+            (2048, InstallState::Validating),                 // Uninstalling
+            (128, InstallState::Validating),                  // FilesCorrupt
+            (32, InstallState::Validating),                   // FilesMissing
+        ];
+
+        for (flags, expected) in cases {
+            assert_eq!(InstallState::from_flags(flags), expected, "flags={}", flags);
+        }
+    }
+
+    #[test]
+    fn launch_argv_honors_quoted_paths_around_command() {
+        let argv = build_launch_argv(
+            "/games/foo/foo.bin",
+            Some(r#""/path/with space/gamemoderun" %command% -novid"#),
+        );
+        assert_eq!(argv, vec!["/path/with space/gamemoderun", "/games/foo/foo.bin", "-novid"]);
+    }
+
+    #[test]
+    fn launch_argv_appends_plain_options_without_command() {
+        let argv = build_launch_argv("/games/foo/foo.bin", Some("-novid -windowed"));
+        assert_eq!(argv, vec!["/games/foo/foo.bin", "-novid", "-windowed"]);
+    }
 }
@@ -0,0 +1,243 @@
+//! Non-Steam game shortcuts, stored per-user in
+//! `userdata/<id>/config/shortcuts.vdf` as binary VDF.
+
+use crate::vdf::{self, VdfValue};
+use arcadia_extension_framework::error::ExtensionError;
+use std::path::Path;
+use tokio::fs;
+
+/// A single non-Steam shortcut entry (an emulator, a launcher, a bare
+/// executable) added through Steam's "Add a Non-Steam Game" flow.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcut {
+    pub appid: u32,
+    pub app_name: String,
+    pub exe: String,
+    pub start_dir: Option<String>,
+    pub icon: Option<String>,
+    pub launch_options: Option<String>,
+    pub is_hidden: bool,
+    pub allow_desktop_config: bool,
+    pub allow_overlay: bool,
+    pub tags: Vec<String>,
+}
+
+/// Derives the appid Steam assigns to a non-Steam shortcut: a CRC32 of the
+/// exe path concatenated with the display name, with the high bit set so it
+/// never collides with a real Steam appid.
+pub fn compute_shortcut_appid(exe: &str, app_name: &str) -> u32 {
+    let mut combined = String::with_capacity(exe.len() + app_name.len());
+    combined.push_str(exe);
+    combined.push_str(app_name);
+    crc32(combined.as_bytes()) | 0x8000_0000
+}
+
+/// Reads `shortcuts.vdf`. A missing file just means no shortcuts have been
+/// added yet, not an error.
+pub async fn read_shortcuts(path: &Path) -> Result<Vec<SteamShortcut>, ExtensionError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let bytes = fs::read(path).await?;
+    let root = vdf::parse_binary_table(&bytes)?;
+    let Some(table) = root.get_path(&["shortcuts"]).and_then(VdfValue::as_table) else {
+        return Ok(Vec::new());
+    };
+
+    let mut indices: Vec<&String> = table.keys().collect();
+    indices.sort_by_key(|key| key.parse::<u32>().unwrap_or(u32::MAX));
+
+    let mut shortcuts = Vec::new();
+    for index in indices {
+        let Some(entry) = table.get(index).and_then(VdfValue::as_table) else {
+            continue;
+        };
+
+        let get_str = |key: &str| entry.get(key).and_then(VdfValue::as_str).map(str::to_string);
+        let get_bool = |key: &str| {
+            entry
+                .get(key)
+                .and_then(VdfValue::as_str)
+                .and_then(|s| s.parse::<u32>().ok())
+                .map(|n| n != 0)
+                .unwrap_or(false)
+        };
+
+        let appid = entry
+            .get("appid")
+            .and_then(VdfValue::as_str)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        let mut tags: Vec<(String, String)> = entry
+            .get("tags")
+            .and_then(VdfValue::as_table)
+            .map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        tags.sort_by_key(|(index, _)| index.parse::<u32>().unwrap_or(u32::MAX));
+
+        shortcuts.push(SteamShortcut {
+            appid,
+            app_name: get_str("AppName").unwrap_or_default(),
+            exe: get_str("Exe").unwrap_or_default(),
+            start_dir: get_str("StartDir"),
+            icon: get_str("icon"),
+            launch_options: get_str("LaunchOptions"),
+            is_hidden: get_bool("IsHidden"),
+            allow_desktop_config: get_bool("AllowDesktopConfig"),
+            allow_overlay: get_bool("AllowOverlay"),
+            tags: tags.into_iter().map(|(_, value)| value).collect(),
+        });
+    }
+
+    Ok(shortcuts)
+}
+
+/// Writes `shortcuts.vdf` back out in binary VDF format, creating the parent
+/// `config` directory if needed.
+pub async fn write_shortcuts(path: &Path, shortcuts: &[SteamShortcut]) -> Result<(), ExtensionError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let mut buf = Vec::new();
+    write_key(&mut buf, TAG_TABLE, "shortcuts");
+    for (index, shortcut) in shortcuts.iter().enumerate() {
+        write_key(&mut buf, TAG_TABLE, &index.to_string());
+        write_u32(&mut buf, "appid", shortcut.appid);
+        write_str(&mut buf, "AppName", &shortcut.app_name);
+        write_str(&mut buf, "Exe", &shortcut.exe);
+        write_str(&mut buf, "StartDir", shortcut.start_dir.as_deref().unwrap_or(""));
+        write_str(&mut buf, "icon", shortcut.icon.as_deref().unwrap_or(""));
+        write_str(&mut buf, "LaunchOptions", shortcut.launch_options.as_deref().unwrap_or(""));
+        write_u32(&mut buf, "IsHidden", shortcut.is_hidden as u32);
+        write_u32(&mut buf, "AllowDesktopConfig", shortcut.allow_desktop_config as u32);
+        write_u32(&mut buf, "AllowOverlay", shortcut.allow_overlay as u32);
+
+        write_key(&mut buf, TAG_TABLE, "tags");
+        for (tag_index, tag) in shortcut.tags.iter().enumerate() {
+            write_str(&mut buf, &tag_index.to_string(), tag);
+        }
+        buf.push(TAG_END); // close tags
+
+        buf.push(TAG_END); // close this shortcut entry
+    }
+    buf.push(TAG_END); // close shortcuts table
+    buf.push(TAG_END); // close root
+
+    fs::write(path, buf).await?;
+    Ok(())
+}
+
+const TAG_TABLE: u8 = 0x00;
+const TAG_STRING: u8 = 0x01;
+const TAG_U32: u8 = 0x02;
+const TAG_END: u8 = 0x08;
+
+fn write_key(buf: &mut Vec<u8>, tag: u8, key: &str) {
+    buf.push(tag);
+    buf.extend_from_slice(key.as_bytes());
+    buf.push(0);
+}
+
+fn write_str(buf: &mut Vec<u8>, key: &str, value: &str) {
+    write_key(buf, TAG_STRING, key);
+    buf.extend_from_slice(value.as_bytes());
+    buf.push(0);
+}
+
+fn write_u32(buf: &mut Vec<u8>, key: &str, value: u32) {
+    write_key(buf, TAG_U32, key);
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+/// CRC-32 (IEEE 802.3 polynomial 0xEDB88320), matching what Steam uses to
+/// derive shortcut appids.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_shortcuts() -> Vec<SteamShortcut> {
+        vec![
+            SteamShortcut {
+                appid: compute_shortcut_appid("/games/foo/foo.bin", "Foo"),
+                app_name: "Foo".to_string(),
+                exe: "/games/foo/foo.bin".to_string(),
+                start_dir: Some("/games/foo".to_string()),
+                icon: Some("/games/foo/icon.png".to_string()),
+                launch_options: Some("-novid".to_string()),
+                is_hidden: false,
+                allow_desktop_config: true,
+                allow_overlay: true,
+                tags: vec!["Favorites".to_string()],
+            },
+            SteamShortcut {
+                appid: compute_shortcut_appid("/games/bar/bar.bin", "Bar"),
+                app_name: "Bar".to_string(),
+                exe: "/games/bar/bar.bin".to_string(),
+                start_dir: None,
+                icon: None,
+                launch_options: None,
+                is_hidden: true,
+                allow_desktop_config: false,
+                allow_overlay: false,
+                tags: Vec::new(),
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn write_then_read_round_trips_shortcuts() {
+        let dir = std::env::temp_dir().join(format!("arcadia-shortcuts-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("shortcuts.vdf");
+
+        let original = sample_shortcuts();
+        write_shortcuts(&path, &original).await.unwrap();
+        let read_back = read_shortcuts(&path).await.unwrap();
+
+        assert_eq!(read_back.len(), original.len());
+        for (expected, actual) in original.iter().zip(read_back.iter()) {
+            assert_eq!(actual.appid, expected.appid);
+            assert_eq!(actual.app_name, expected.app_name);
+            assert_eq!(actual.exe, expected.exe);
+            assert_eq!(actual.start_dir.as_deref().unwrap_or(""), expected.start_dir.as_deref().unwrap_or(""));
+            assert_eq!(actual.icon.as_deref().unwrap_or(""), expected.icon.as_deref().unwrap_or(""));
+            assert_eq!(
+                actual.launch_options.as_deref().unwrap_or(""),
+                expected.launch_options.as_deref().unwrap_or("")
+            );
+            assert_eq!(actual.is_hidden, expected.is_hidden);
+            assert_eq!(actual.allow_desktop_config, expected.allow_desktop_config);
+            assert_eq!(actual.allow_overlay, expected.allow_overlay);
+            assert_eq!(actual.tags, expected.tags);
+        }
+
+        let _ = fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn read_shortcuts_missing_file_returns_empty() {
+        let path = std::env::temp_dir().join(format!("arcadia-shortcuts-missing-{}.vdf", std::process::id()));
+        let shortcuts = read_shortcuts(&path).await.unwrap();
+        assert!(shortcuts.is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+//! Local Steam account discovery: `config/loginusers.vdf` maps SteamID64 to
+//! account names, and each account's `userdata/<accountid>/config/localconfig.vdf`
+//! carries its own per-game settings (e.g. `LaunchOptions`).
+
+use crate::vdf;
+use arcadia_extension_framework::error::ExtensionError;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tokio::fs;
+
+/// SteamID64 of the first individual account, used to recover the 32-bit
+/// account id (and therefore the `userdata/<id>` directory name) from a
+/// SteamID64.
+const STEAM_ID64_INDIVIDUAL_BASE: u64 = 76561197960265728;
+
+/// A local Steam account, as recorded in `config/loginusers.vdf`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SteamUser {
+    pub steam_id64: u64,
+    pub account_name: String,
+    pub persona_name: Option<String>,
+    pub most_recent: bool,
+}
+
+/// Converts a SteamID64 to the 32-bit account id used as the `userdata`
+/// directory name (Steam calls this a "SteamID3" in most tooling).
+pub fn steamid64_to_account_id(steam_id64: u64) -> u32 {
+    steam_id64.saturating_sub(STEAM_ID64_INDIVIDUAL_BASE) as u32
+}
+
+/// Parses `config/loginusers.vdf`. A missing file just means no one has ever
+/// logged into this Steam install.
+pub async fn read_login_users(path: &Path) -> Result<Vec<SteamUser>, ExtensionError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    let root = vdf::parse_text(&content)?;
+    let Some(table) = root.get_path(&["users"]).and_then(vdf::VdfValue::as_table) else {
+        return Ok(Vec::new());
+    };
+
+    let mut users: Vec<SteamUser> = table
+        .iter()
+        .filter_map(|(steamid_str, value)| {
+            let entry = value.as_table()?;
+            let steam_id64 = steamid_str.parse::<u64>().ok()?;
+            Some(SteamUser {
+                steam_id64,
+                account_name: entry.get("AccountName").and_then(vdf::VdfValue::as_str).unwrap_or_default().to_string(),
+                persona_name: entry.get("PersonaName").and_then(vdf::VdfValue::as_str).map(str::to_string),
+                most_recent: entry.get("mostrecent").and_then(vdf::VdfValue::as_str) == Some("1"),
+            })
+        })
+        .collect();
+    users.sort_by_key(|u| u.steam_id64);
+
+    Ok(users)
+}
+
+/// Picks which account's config to load: the one `loginusers.vdf` flags as
+/// most recent, or the first account if none is flagged.
+pub fn most_recent_user(users: &[SteamUser]) -> Option<&SteamUser> {
+    users.iter().find(|u| u.most_recent).or_else(|| users.first())
+}
+
+/// Reads every app's `LaunchOptions` out of an account's `localconfig.vdf`,
+/// keyed by appid. A missing file yields an empty map rather than an error.
+pub async fn read_launch_options(path: &Path) -> Result<std::collections::HashMap<u32, String>, ExtensionError> {
+    if !path.exists() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let content = fs::read_to_string(path).await?;
+    let root = vdf::parse_text(&content)?;
+    let Some(apps) = root
+        .get_path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"])
+        .and_then(vdf::VdfValue::as_table)
+    else {
+        return Ok(std::collections::HashMap::new());
+    };
+
+    Ok(apps
+        .iter()
+        .filter_map(|(appid_str, value)| {
+            let appid = appid_str.parse::<u32>().ok()?;
+            let launch_options = value.as_table()?.get("LaunchOptions")?.as_str()?;
+            Some((appid, launch_options.to_string()))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(steam_id64: u64, most_recent: bool) -> SteamUser {
+        SteamUser {
+            steam_id64,
+            account_name: format!("user{}", steam_id64),
+            persona_name: None,
+            most_recent,
+        }
+    }
+
+    #[test]
+    fn steamid64_to_account_id_subtracts_individual_base() {
+        assert_eq!(steamid64_to_account_id(STEAM_ID64_INDIVIDUAL_BASE), 0);
+        assert_eq!(steamid64_to_account_id(STEAM_ID64_INDIVIDUAL_BASE + 12345), 12345);
+    }
+
+    #[test]
+    fn steamid64_to_account_id_saturates_below_the_base() {
+        assert_eq!(steamid64_to_account_id(0), 0);
+        assert_eq!(steamid64_to_account_id(STEAM_ID64_INDIVIDUAL_BASE - 1), 0);
+    }
+
+    #[test]
+    fn most_recent_user_prefers_flagged_account() {
+        let users = vec![user(1, false), user(2, true), user(3, false)];
+        assert_eq!(most_recent_user(&users).unwrap().steam_id64, 2);
+    }
+
+    #[test]
+    fn most_recent_user_falls_back_to_first_when_none_flagged() {
+        let users = vec![user(1, false), user(2, false)];
+        assert_eq!(most_recent_user(&users).unwrap().steam_id64, 1);
+    }
+
+    #[test]
+    fn most_recent_user_is_none_for_empty_list() {
+        assert!(most_recent_user(&[]).is_none());
+    }
+}
@@ -0,0 +1,461 @@
+//! Minimal VDF (Valve Data Format) support: a recursive-descent parser for the
+//! text flavor used by `.acf` manifests and `config/*.vdf`, plus a reader for
+//! the binary flavor used by `appcache/appinfo.vdf`.
+
+use arcadia_extension_framework::error::ExtensionError;
+use std::collections::HashMap;
+
+/// A parsed VDF node. Text VDF only ever produces strings and nested tables;
+/// binary VDF additionally distinguishes integers (see [`parse_appinfo`]),
+/// which are folded into strings here so callers have one lookup API.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VdfValue {
+    String(String),
+    Table(HashMap<String, VdfValue>),
+}
+
+impl VdfValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::String(s) => Some(s),
+            VdfValue::Table(_) => None,
+        }
+    }
+
+    pub fn as_table(&self) -> Option<&HashMap<String, VdfValue>> {
+        match self {
+            VdfValue::Table(t) => Some(t),
+            VdfValue::String(_) => None,
+        }
+    }
+
+    /// Walks a chain of table keys, case-insensitively, returning the node at
+    /// the end of the path if every segment resolves.
+    pub fn get_path(&self, path: &[&str]) -> Option<&VdfValue> {
+        let mut current = self;
+        for segment in path {
+            let table = current.as_table()?;
+            current = table.iter().find_map(|(k, v)| {
+                if k.eq_ignore_ascii_case(segment) {
+                    Some(v)
+                } else {
+                    None
+                }
+            })?;
+        }
+        Some(current)
+    }
+
+    /// Convenience wrapper over [`get_path`](Self::get_path) for the common
+    /// case of wanting a string leaf.
+    pub fn get_str(&self, path: &[&str]) -> Option<&str> {
+        self.get_path(path)?.as_str()
+    }
+}
+
+/// Parses a complete text-VDF document (e.g. the contents of an `.acf` file)
+/// into a single root [`VdfValue::Table`].
+///
+/// Unlike a flat line scanner, this tokenizes `"`-quoted strings and `{ }`
+/// braces so nested sections (`UserConfig`, `MountedDepots`, ...) parse
+/// correctly.
+pub fn parse_text(input: &str) -> Result<VdfValue, ExtensionError> {
+    let tokens = tokenize(input)?;
+    let mut pos = 0;
+    let root = parse_table(&tokens, &mut pos)?;
+    Ok(VdfValue::Table(root))
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ExtensionError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            // Preserve the escaped character verbatim; VDF only
+                            // escapes '"', '\\' and newlines in practice.
+                            if let Some(escaped) = chars.next() {
+                                value.push(escaped);
+                            }
+                        }
+                        Some('"') => break,
+                        Some(other) => value.push(other),
+                        None => {
+                            return Err(ExtensionError::Validation(
+                                "unterminated string in VDF document".to_string(),
+                            ))
+                        }
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            '{' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            '}' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '/' => {
+                // `//` line comments, occasionally seen in hand-edited VDF.
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            _ => {
+                // Bare (unquoted) tokens are rare but appear in some macro
+                // conditionals; consume up to the next delimiter as a string.
+                let mut value = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                if !value.is_empty() {
+                    tokens.push(Token::Str(value));
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_table(
+    tokens: &[Token],
+    pos: &mut usize,
+) -> Result<HashMap<String, VdfValue>, ExtensionError> {
+    let mut table = HashMap::new();
+
+    while *pos < tokens.len() {
+        match &tokens[*pos] {
+            Token::Close => {
+                *pos += 1;
+                return Ok(table);
+            }
+            Token::Str(key) => {
+                let key = key.clone();
+                *pos += 1;
+                match tokens.get(*pos) {
+                    Some(Token::Str(value)) => {
+                        table.insert(key, VdfValue::String(value.clone()));
+                        *pos += 1;
+                    }
+                    Some(Token::Open) => {
+                        *pos += 1;
+                        let nested = parse_table(tokens, pos)?;
+                        table.insert(key, VdfValue::Table(nested));
+                    }
+                    _ => {
+                        return Err(ExtensionError::Validation(format!(
+                            "expected value after key \"{}\"",
+                            key
+                        )))
+                    }
+                }
+            }
+            Token::Open => {
+                return Err(ExtensionError::Validation(
+                    "unexpected '{' without preceding key".to_string(),
+                ))
+            }
+        }
+    }
+
+    Ok(table)
+}
+
+/// One app's record inside `appcache/appinfo.vdf`.
+#[derive(Debug, Clone)]
+pub struct AppInfoEntry {
+    pub app_id: u32,
+    pub info_state: u32,
+    pub last_updated: u32,
+    pub pics_token: u64,
+    pub sha1: [u8; 20],
+    pub change_number: u32,
+    pub data: VdfValue,
+}
+
+/// Parses `appcache/appinfo.vdf`.
+///
+/// Layout: a `u32` magic, a `u32` universe, then a sequence of entries each
+/// shaped as:
+/// `app_id: u32, size: u32` (size of the remainder of the entry; read and
+/// skipped for every entry, whatever the magic), `info_state: u32`,
+/// `last_updated: u32`, `pics_token: u64`,
+/// `sha1: [u8; 20]`, `change_number: u32`, then a binary KV tree, all
+/// terminated by an `app_id` of `0`.
+pub fn parse_appinfo(bytes: &[u8]) -> Result<Vec<AppInfoEntry>, ExtensionError> {
+    let mut cursor = Cursor::new(bytes);
+    let _magic = cursor.read_u32()?;
+    let _universe = cursor.read_u32()?;
+
+    let mut entries = Vec::new();
+    loop {
+        let app_id = cursor.read_u32()?;
+        if app_id == 0 {
+            break;
+        }
+
+        let _entry_size = cursor.read_u32()?;
+        let info_state = cursor.read_u32()?;
+        let last_updated = cursor.read_u32()?;
+        let pics_token = cursor.read_u64()?;
+        let sha1 = cursor.read_bytes::<20>()?;
+        let change_number = cursor.read_u32()?;
+        let data = read_binary_table(&mut cursor)?;
+
+        entries.push(AppInfoEntry {
+            app_id,
+            info_state,
+            last_updated,
+            pics_token,
+            sha1,
+            change_number,
+            data,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Parses a bare binary-VDF table, i.e. a binary VDF document with no
+/// `appinfo.vdf`-style header in front of it (e.g. `shortcuts.vdf`).
+pub fn parse_binary_table(bytes: &[u8]) -> Result<VdfValue, ExtensionError> {
+    let mut cursor = Cursor::new(bytes);
+    read_binary_table(&mut cursor)
+}
+
+const BIN_TABLE_END: u8 = 0x08;
+const BIN_TYPE_TABLE: u8 = 0x00;
+const BIN_TYPE_STRING: u8 = 0x01;
+const BIN_TYPE_U32: u8 = 0x02;
+
+fn read_binary_table(cursor: &mut Cursor) -> Result<VdfValue, ExtensionError> {
+    let mut table = HashMap::new();
+
+    loop {
+        let tag = cursor.read_u8()?;
+        if tag == BIN_TABLE_END {
+            break;
+        }
+
+        let key = cursor.read_cstr()?;
+        let value = match tag {
+            BIN_TYPE_TABLE => read_binary_table(cursor)?,
+            BIN_TYPE_STRING => VdfValue::String(cursor.read_cstr()?),
+            BIN_TYPE_U32 => VdfValue::String(cursor.read_u32()?.to_string()),
+            other => {
+                return Err(ExtensionError::Validation(format!(
+                    "unsupported binary VDF node type 0x{:02x}",
+                    other
+                )))
+            }
+        };
+        table.insert(key, value);
+    }
+
+    Ok(VdfValue::Table(table))
+}
+
+/// Tiny bounds-checked byte cursor; binary VDF has no length prefixes so every
+/// read has to be validated against the remaining slice.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ExtensionError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or_else(|| {
+                ExtensionError::Validation("unexpected end of binary VDF data".to_string())
+            })?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, ExtensionError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ExtensionError> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().unwrap();
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, ExtensionError> {
+        let bytes: [u8; 8] = self.take(8)?.try_into().unwrap();
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], ExtensionError> {
+        let bytes: [u8; N] = self.take(N)?.try_into().unwrap();
+        Ok(bytes)
+    }
+
+    fn read_cstr(&mut self) -> Result<String, ExtensionError> {
+        let start = self.pos;
+        while self.pos < self.bytes.len() && self.bytes[self.pos] != 0 {
+            self.pos += 1;
+        }
+        if self.pos >= self.bytes.len() {
+            return Err(ExtensionError::Validation(
+                "unterminated string in binary VDF data".to_string(),
+            ));
+        }
+        let slice = &self.bytes[start..self.pos];
+        self.pos += 1; // skip the NUL terminator
+        Ok(String::from_utf8_lossy(slice).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_flat_key_values() {
+        let parsed = parse_text(r#""AppState" { "appid" "440" "name" "Team Fortress 2" }"#).unwrap();
+        assert_eq!(parsed.get_str(&["AppState", "appid"]), Some("440"));
+        assert_eq!(parsed.get_str(&["AppState", "name"]), Some("Team Fortress 2"));
+    }
+
+    #[test]
+    fn parses_nested_tables_like_user_config_and_mounted_depots() {
+        let parsed = parse_text(
+            r#"
+            "AppState"
+            {
+                "appid" "440"
+                "UserConfig"
+                {
+                    "language" "english"
+                }
+                "MountedDepots"
+                {
+                    "441" "1234567890"
+                    "442" "2345678901"
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(parsed.get_str(&["AppState", "UserConfig", "language"]), Some("english"));
+        assert_eq!(parsed.get_str(&["AppState", "MountedDepots", "441"]), Some("1234567890"));
+        assert_eq!(parsed.get_str(&["AppState", "MountedDepots", "442"]), Some("2345678901"));
+    }
+
+    #[test]
+    fn handles_escaped_quotes_and_backslashes_inside_strings() {
+        let parsed = parse_text(r#""AppState" { "installdir" "C:\\Games\\Some \"Game\"" }"#).unwrap();
+        assert_eq!(
+            parsed.get_str(&["AppState", "installdir"]),
+            Some(r#"C:\Games\Some "Game""#)
+        );
+    }
+
+    #[test]
+    fn lookup_is_case_insensitive_and_missing_path_is_none() {
+        let parsed = parse_text(r#""AppState" { "AppId" "440" }"#).unwrap();
+        assert_eq!(parsed.get_str(&["appstate", "APPID"]), Some("440"));
+        assert_eq!(parsed.get_str(&["AppState", "missing"]), None);
+    }
+
+    #[test]
+    fn rejects_unterminated_strings() {
+        assert!(parse_text(r#""AppState" { "appid" "440"#).is_err());
+    }
+
+    fn push_cstr(buf: &mut Vec<u8>, s: &str) {
+        buf.extend_from_slice(s.as_bytes());
+        buf.push(0);
+    }
+
+    #[test]
+    fn parse_appinfo_reads_header_and_one_entry() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&0x0756_2433u32.to_le_bytes()); // magic
+        buf.extend_from_slice(&0x0000_0002u32.to_le_bytes()); // universe
+
+        buf.extend_from_slice(&440u32.to_le_bytes()); // app_id
+        buf.extend_from_slice(&0u32.to_le_bytes()); // entry_size (unused by the parser)
+        buf.extend_from_slice(&2u32.to_le_bytes()); // info_state
+        buf.extend_from_slice(&1_700_000_000u32.to_le_bytes()); // last_updated
+        buf.extend_from_slice(&123_456_789u64.to_le_bytes()); // pics_token
+        buf.extend_from_slice(&[7u8; 20]); // sha1
+        buf.extend_from_slice(&99u32.to_le_bytes()); // change_number
+
+        // data: { "name" "Team Fortress 2", "depots" { "1" <u32 100> } }
+        buf.push(BIN_TYPE_STRING);
+        push_cstr(&mut buf, "name");
+        push_cstr(&mut buf, "Team Fortress 2");
+        buf.push(BIN_TYPE_TABLE);
+        push_cstr(&mut buf, "depots");
+        buf.push(BIN_TYPE_U32);
+        push_cstr(&mut buf, "1");
+        buf.extend_from_slice(&100u32.to_le_bytes());
+        buf.push(BIN_TABLE_END); // close depots
+        buf.push(BIN_TABLE_END); // close entry's data table
+
+        buf.extend_from_slice(&0u32.to_le_bytes()); // terminator app_id
+
+        let entries = parse_appinfo(&buf).unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let entry = &entries[0];
+        assert_eq!(entry.app_id, 440);
+        assert_eq!(entry.info_state, 2);
+        assert_eq!(entry.last_updated, 1_700_000_000);
+        assert_eq!(entry.pics_token, 123_456_789);
+        assert_eq!(entry.sha1, [7u8; 20]);
+        assert_eq!(entry.change_number, 99);
+        assert_eq!(entry.data.get_str(&["name"]), Some("Team Fortress 2"));
+        assert_eq!(entry.data.get_str(&["depots", "1"]), Some("100"));
+    }
+
+    #[test]
+    fn parse_appinfo_stops_at_terminator_without_trailing_entries() {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&0x0756_2433u32.to_le_bytes()); // magic
+        buf.extend_from_slice(&0x0000_0002u32.to_le_bytes()); // universe
+        buf.extend_from_slice(&0u32.to_le_bytes()); // terminator app_id, no entries
+
+        let entries = parse_appinfo(&buf).unwrap();
+        assert!(entries.is_empty());
+    }
+}